@@ -0,0 +1,165 @@
+//! Decodes a response body according to its `Transfer-Encoding`.
+//!
+//! `TransferEncoding` only describes the codings that were applied to a body;
+//! nothing in `header` actually undoes them. `TransferDecoder` closes that gap by
+//! composing a chain of `Read` stages that reverses the codings, so callers get
+//! the plain entity body instead of the raw framed bytes off the wire.
+
+use std::io::{self, Read};
+
+use flate2::read::{DeflateDecoder, GzDecoder};
+
+use header::common::transfer_encoding::Encoding;
+use header::common::transfer_encoding::Encoding::{Chunked, Gzip, Deflate, Compress, Brotli,
+                                                    Identity, Trailers, EncodingExt};
+
+/// Wraps a reader, undoing the codings listed in a `Transfer-Encoding` header.
+///
+/// Stages are composed in reverse order of the coding list, since the last
+/// coding applied by the sender is the first one that must be undone.
+pub struct TransferDecoder {
+    inner: Box<Read + 'static>,
+}
+
+impl TransferDecoder {
+    /// Builds a decoder for `reader`, driven by `encodings` (typically the
+    /// contents of a `TransferEncoding` header).
+    pub fn new<R: Read + 'static>(reader: R, encodings: &[Encoding]) -> io::Result<TransferDecoder> {
+        let mut stage: Box<Read + 'static> = Box::new(reader);
+        for encoding in encodings.iter().rev() {
+            stage = try!(wrap(stage, encoding));
+        }
+        Ok(TransferDecoder { inner: stage })
+    }
+}
+
+impl Read for TransferDecoder {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+fn wrap(inner: Box<Read + 'static>, encoding: &Encoding) -> io::Result<Box<Read + 'static>> {
+    match *encoding {
+        Chunked => Ok(Box::new(ChunkedDecoder::new(inner))),
+        Gzip => {
+            let gz = try!(GzDecoder::new(inner));
+            Ok(Box::new(gz))
+        }
+        Deflate => Ok(Box::new(DeflateDecoder::new(inner))),
+        Identity | Compress | Brotli | Trailers | EncodingExt(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot decode transfer-coding `{}`", encoding)
+        )),
+    }
+}
+
+/// Undoes `chunked` framing: reads a hex chunk-size line, then that many bytes of
+/// data and its trailing CRLF, repeating until a zero-size chunk is seen, after
+/// which any trailer headers are consumed up to the final blank line.
+struct ChunkedDecoder {
+    inner: Box<Read + 'static>,
+    state: ChunkedState,
+    remaining: u64,
+}
+
+enum ChunkedState {
+    ReadSize,
+    ReadData,
+    ReadDataTrailer,
+    ReadTrailers,
+    Eof,
+}
+
+impl ChunkedDecoder {
+    fn new(inner: Box<Read + 'static>) -> ChunkedDecoder {
+        ChunkedDecoder {
+            inner: inner,
+            state: ChunkedState::ReadSize,
+            remaining: 0,
+        }
+    }
+
+    fn read_byte(&mut self) -> io::Result<u8> {
+        let mut byte = [0u8; 1];
+        match try!(self.inner.read(&mut byte)) {
+            1 => Ok(byte[0]),
+            _ => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "unexpected end of chunked body")),
+        }
+    }
+
+    fn read_line(&mut self) -> io::Result<String> {
+        let mut line = Vec::new();
+        loop {
+            let byte = try!(self.read_byte());
+            if byte == b'\n' {
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                break;
+            }
+            line.push(byte);
+        }
+        String::from_utf8(line).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "invalid chunk header")
+        })
+    }
+
+    fn read_chunk_size(&mut self) -> io::Result<u64> {
+        let line = try!(self.read_line());
+        // A chunk-size line may carry chunk-extensions after a `;`; we don't
+        // support any, but must still ignore them.
+        let size = line.splitn(2, ';').next().unwrap_or("").trim();
+        u64::from_str_radix(size, 16).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "invalid chunk size")
+        })
+    }
+}
+
+impl Read for ChunkedDecoder {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match self.state {
+                ChunkedState::ReadSize => {
+                    self.remaining = try!(self.read_chunk_size());
+                    self.state = if self.remaining == 0 {
+                        ChunkedState::ReadTrailers
+                    } else {
+                        ChunkedState::ReadData
+                    };
+                }
+                ChunkedState::ReadData => {
+                    if buf.len() == 0 {
+                        return Ok(0);
+                    }
+                    let want = ::std::cmp::min(buf.len() as u64, self.remaining) as usize;
+                    let read = try!(self.inner.read(&mut buf[..want]));
+                    if read == 0 {
+                        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "unexpected end of chunk data"));
+                    }
+                    self.remaining -= read as u64;
+                    if self.remaining == 0 {
+                        self.state = ChunkedState::ReadDataTrailer;
+                    }
+                    return Ok(read);
+                }
+                ChunkedState::ReadDataTrailer => {
+                    let line = try!(self.read_line());
+                    if !line.is_empty() {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, "malformed chunk"));
+                    }
+                    self.state = ChunkedState::ReadSize;
+                }
+                ChunkedState::ReadTrailers => {
+                    let line = try!(self.read_line());
+                    if line.is_empty() {
+                        self.state = ChunkedState::Eof;
+                    }
+                }
+                ChunkedState::Eof => {
+                    return Ok(0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use header::{Header, HeaderFormat};
+use std::fmt;
+use std::str::FromStr;
+use header::shared::util::{from_comma_delimited, fmt_comma_delimited};
+
+use header::common::transfer_encoding::Encoding;
+use header::common::transfer_encoding::Encoding::Identity;
+
+/// The `Accept-Encoding` header.
+///
+/// This header lists the content codings a client is willing to accept in a
+/// response, each with an optional quality value expressing preference.
+///
+/// ```notrust
+/// Accept-Encoding: gzip;q=1.0, br;q=0.8, identity;q=0.5, *;q=0
+/// ```
+///
+/// Use `preferred` to pick the best coding a server can produce for a given
+/// request.
+#[derive(Clone, PartialEq, Show)]
+pub struct AcceptEncoding(pub Vec<QualityItem>);
+
+deref!(AcceptEncoding => Vec<QualityItem>);
+
+/// A quality value, a fixed-point number from `0` to `1000` representing `0.000`
+/// to `1.000` as used in HTTP content negotiation headers.
+pub type Quality = u16;
+
+/// The quality value implied when none is given, equivalent to `q=1.0`.
+pub const DEFAULT_QUALITY: Quality = 1000;
+
+/// One entry of an `Accept-Encoding` header: either a specific `Encoding`, or the
+/// `*` wildcard, paired with a quality value.
+#[derive(Clone, PartialEq, Show)]
+pub struct QualityItem {
+    /// The encoding this entry applies to, or `None` for the `*` wildcard.
+    pub encoding: Option<Encoding>,
+    /// The associated quality value, from `0` (forbidden) to `1000` (`q=1.0`).
+    pub quality: Quality,
+}
+
+impl fmt::String for QualityItem {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.encoding {
+            Some(ref encoding) => try!(fmt::String::fmt(encoding, fmt)),
+            None => try!(write!(fmt, "*")),
+        }
+        if self.quality != DEFAULT_QUALITY {
+            try!(write!(fmt, ";q={}", format_quality(self.quality)));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for QualityItem {
+    fn from_str(s: &str) -> Option<QualityItem> {
+        let mut parts = s.split(';');
+        let coding = match parts.next() {
+            Some(coding) => coding.trim(),
+            None => return None,
+        };
+
+        let encoding = if coding == "*" {
+            None
+        } else {
+            Encoding::from_str(coding)
+        };
+
+        let quality = match parts.next() {
+            Some(param) => {
+                let param = param.trim();
+                if !param.starts_with("q=") {
+                    return None;
+                }
+                match parse_quality(&param[2..]) {
+                    Some(q) => q,
+                    None => return None,
+                }
+            }
+            None => DEFAULT_QUALITY,
+        };
+
+        Some(QualityItem { encoding: encoding, quality: quality })
+    }
+}
+
+fn parse_quality(s: &str) -> Option<Quality> {
+    let parts: Vec<&str> = s.trim().splitn(2, '.').collect();
+    let whole = match FromStr::from_str(parts[0]) {
+        Some(whole) => whole,
+        None => return None,
+    };
+    if whole > 1u16 {
+        return None;
+    }
+
+    let fraction: Quality = match parts.get(1) {
+        Some(frac) if frac.len() <= 3 && frac.len() > 0 => {
+            let mut padded = frac.to_string();
+            while padded.len() < 3 {
+                padded.push('0');
+            }
+            match FromStr::from_str(padded.as_slice()) {
+                Some(fraction) => fraction,
+                None => return None,
+            }
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+
+    let quality = whole * 1000 + fraction;
+    if quality > 1000 { None } else { Some(quality) }
+}
+
+fn format_quality(q: Quality) -> String {
+    if q == 0 {
+        return "0".to_string();
+    }
+    if q >= DEFAULT_QUALITY {
+        return "1".to_string();
+    }
+    let mut s = format!("{:03}", q);
+    while s.as_slice().ends_with("0") {
+        s.pop();
+    }
+    format!("0.{}", s)
+}
+
+impl Header for AcceptEncoding {
+    fn header_name(_: Option<AcceptEncoding>) -> &'static str {
+        "Accept-Encoding"
+    }
+
+    fn parse_header(raw: &[Vec<u8>]) -> Option<AcceptEncoding> {
+        from_comma_delimited(raw).map(AcceptEncoding)
+    }
+}
+
+impl HeaderFormat for AcceptEncoding {
+    fn fmt_header(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt_comma_delimited(fmt, &self[])
+    }
+}
+
+impl AcceptEncoding {
+    /// Picks the best coding to use for a response, given the codings a server
+    /// is able to produce in `available`.
+    ///
+    /// Implements the negotiation rules of RFC 7231 §5.3.4: an entry with
+    /// `q=0` explicitly forbids that coding, `*` matches any coding not
+    /// otherwise listed (and `*;q=0` forbids everything unlisted), and
+    /// `identity` is acceptable unless explicitly forbidden. The coding in
+    /// `available` with the highest matching `q` wins; ties are broken by
+    /// `available`'s ordering. Returns `None` if nothing acceptable remains.
+    pub fn preferred(&self, available: &[Encoding]) -> Option<Encoding> {
+        let wildcard_q = self.iter()
+            .find(|item| item.encoding.is_none())
+            .map(|item| item.quality);
+
+        let mut best: Option<(Encoding, Quality)> = None;
+
+        for coding in available.iter() {
+            let explicit = self.iter().find(|item| {
+                item.encoding.as_ref() == Some(coding)
+            });
+
+            let q = match explicit {
+                Some(item) => item.quality,
+                None => match wildcard_q {
+                    Some(q) => q,
+                    None if *coding == Identity => DEFAULT_QUALITY,
+                    None => continue,
+                },
+            };
+
+            if q == 0 {
+                continue;
+            }
+
+            let better = match best {
+                Some((_, best_q)) => q > best_q,
+                None => true,
+            };
+
+            if better {
+                best = Some((coding.clone(), q));
+            }
+        }
+
+        best.map(|(encoding, _)| encoding)
+    }
+}
+
+bench_header!(normal, AcceptEncoding, { vec![b"gzip;q=1.0, br;q=0.8, identity;q=0.5, *;q=0".to_vec()] });
@@ -0,0 +1,40 @@
+use header::{Header, HeaderFormat};
+use std::fmt;
+use header::shared::util::{from_comma_delimited, fmt_comma_delimited};
+
+use header::common::transfer_encoding::Encoding;
+
+/// The `Content-Encoding` header.
+///
+/// This header describes an end-to-end transformation that has been applied
+/// to the body, as opposed to `Transfer-Encoding`, which describes a
+/// hop-by-hop framing of the message. It can be comma-separated, including
+/// multiple encodings.
+///
+/// ```notrust
+/// Content-Encoding: gzip
+/// ```
+///
+/// The implementation uses a vector of `Encoding` values.
+#[derive(Clone, PartialEq, Show)]
+pub struct ContentEncoding(pub Vec<Encoding>);
+
+deref!(ContentEncoding => Vec<Encoding>);
+
+impl Header for ContentEncoding {
+    fn header_name(_: Option<ContentEncoding>) -> &'static str {
+        "Content-Encoding"
+    }
+
+    fn parse_header(raw: &[Vec<u8>]) -> Option<ContentEncoding> {
+        from_comma_delimited(raw).map(ContentEncoding)
+    }
+}
+
+impl HeaderFormat for ContentEncoding {
+    fn fmt_header(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt_comma_delimited(fmt, &self[])
+    }
+}
+
+bench_header!(single, ContentEncoding, { vec![b"gzip".to_vec()] });
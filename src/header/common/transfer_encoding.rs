@@ -3,7 +3,7 @@ use std::fmt;
 use std::str::FromStr;
 use header::shared::util::{from_comma_delimited, fmt_comma_delimited};
 
-use self::Encoding::{Chunked, Gzip, Deflate, Compress, EncodingExt};
+use self::Encoding::{Chunked, Gzip, Deflate, Compress, Brotli, Identity, Trailers, EncodingExt};
 
 /// The `Transfer-Encoding` header.
 ///
@@ -43,6 +43,12 @@ pub enum Encoding {
     Deflate,
     /// The `compress` encoding.
     Compress,
+    /// The `br` encoding.
+    Brotli,
+    /// The `identity` encoding.
+    Identity,
+    /// The `trailers` encoding.
+    Trailers,
     /// Some other encoding that is less common, can be any String.
     EncodingExt(String)
 }
@@ -54,6 +60,9 @@ impl fmt::String for Encoding {
             Gzip => "gzip",
             Deflate => "deflate",
             Compress => "compress",
+            Brotli => "br",
+            Identity => "identity",
+            Trailers => "trailers",
             EncodingExt(ref s) => s.as_slice()
         })
     }
@@ -66,6 +75,9 @@ impl FromStr for Encoding {
             "deflate" => Some(Deflate),
             "gzip" => Some(Gzip),
             "compress" => Some(Compress),
+            "br" => Some(Brotli),
+            "identity" => Some(Identity),
+            "trailers" => Some(Trailers),
             _ => Some(EncodingExt(s.to_string()))
         }
     }
@@ -87,5 +99,23 @@ impl HeaderFormat for TransferEncoding {
     }
 }
 
+impl TransferEncoding {
+    // `is_valid` checks only where `chunked` appears in this header's own list.
+    // It does not look at any `Content-Length` header, and nothing here removes
+    // a `Content-Length` when a `TransferEncoding` is set on `Headers`.
+
+    /// Returns `true` if this header's codings satisfy RFC 7230 §3.3.1: `chunked`
+    /// appears at most once, and only as the final coding in the list.
+    ///
+    /// A `Transfer-Encoding` failing this check must not be sent alongside it, since
+    /// the recipient would be unable to determine where the message body ends.
+    pub fn is_valid(&self) -> bool {
+        match self.iter().position(|encoding| *encoding == Chunked) {
+            Some(pos) => pos == self.len() - 1,
+            None => true,
+        }
+    }
+}
+
 bench_header!(normal, TransferEncoding, { vec![b"chunked, gzip".to_vec()] });
 bench_header!(ext, TransferEncoding, { vec![b"ext".to_vec()] });